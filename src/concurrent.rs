@@ -0,0 +1,157 @@
+//! A sharded, concurrency-friendly variant of [`ExistsMap`](crate::ExistsMap).
+
+use crate::ExistsItem;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::RwLock;
+
+/// A concurrent version of [`ExistsMap`](crate::ExistsMap), analogous to
+/// `dashmap`'s sharded `DashMap`.
+///
+/// Entries are spread across a fixed number of shards, each guarded by its
+/// own `RwLock`, and an operation only locks the shard its key hashes into.
+/// Two threads touching keys in different shards never block each other.
+/// Like `ExistsMap`, only the hashed key is stored per entry, so
+/// `ConcurrentExistsMap` stays lighter than a keyed concurrent map.
+pub struct ConcurrentExistsMap<K, V> {
+    state: ahash::RandomState,
+    shards: Vec<RwLock<HashMap<ExistsItem<K>, V>>>,
+    shard_bits: u32,
+}
+
+impl<K, V> ConcurrentExistsMap<K, V> {
+    /// Creates a map sharded across roughly one shard per available CPU.
+    pub fn new() -> Self {
+        let cpus = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        Self::with_shards(cpus)
+    }
+
+    /// Creates a map with (at least) `shard_count` shards. The actual shard
+    /// count is rounded up to the next power of two, since a shard is
+    /// chosen from the high bits of the hashed key.
+    pub fn with_shards(shard_count: usize) -> Self {
+        let shard_count = shard_count.max(1).next_power_of_two();
+        let shards = (0..shard_count)
+            .map(|_| RwLock::new(HashMap::new()))
+            .collect();
+        Self {
+            state: ahash::RandomState::new(),
+            shards,
+            shard_bits: shard_count.trailing_zeros(),
+        }
+    }
+
+    /// Returns the number of shards the map is split into.
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    fn shard_for(&self, hashed_key: u64) -> &RwLock<HashMap<ExistsItem<K>, V>> {
+        let index = if self.shard_bits == 0 {
+            0
+        } else {
+            (hashed_key >> (64 - self.shard_bits)) as usize
+        };
+        &self.shards[index]
+    }
+}
+
+impl<K, V> Default for ConcurrentExistsMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> ConcurrentExistsMap<K, V>
+where
+    K: Hash,
+{
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let item = ExistsItem::new(&key, &self.state);
+        let mut shard = self.shard_for(item.hashed_key).write().unwrap();
+        shard.insert(item, value)
+    }
+
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        let item = ExistsItem::new(key, &self.state);
+        let shard = self.shard_for(item.hashed_key).read().unwrap();
+        shard.get(&item).cloned()
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hashed_key = self.state.hash_one(key);
+        let item = ExistsItem::from(hashed_key);
+        let shard = self.shard_for(hashed_key).read().unwrap();
+        shard.contains_key(&item)
+    }
+
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hashed_key = self.state.hash_one(key);
+        let item = ExistsItem::from(hashed_key);
+        let mut shard = self.shard_for(hashed_key).write().unwrap();
+        shard.remove(&item)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_concurrent_exists_map_basic() {
+        let exists_map = ConcurrentExistsMap::<i32, i32>::with_shards(4);
+
+        for i in 0..50 {
+            exists_map.insert(i, i);
+        }
+        assert!(exists_map.contains(&10));
+        assert_eq!(exists_map.get(&10), Some(10));
+        assert_eq!(exists_map.remove(&10), Some(10));
+        assert!(!exists_map.contains(&10));
+        assert_eq!(exists_map.get(&10), None);
+    }
+
+    #[test]
+    fn test_concurrent_exists_map_rounds_shard_count_to_power_of_two() {
+        let exists_map = ConcurrentExistsMap::<i32, i32>::with_shards(5);
+        assert_eq!(exists_map.shard_count(), 8);
+    }
+
+    #[test]
+    fn test_concurrent_exists_map_across_threads() {
+        let exists_map = Arc::new(ConcurrentExistsMap::<i32, i32>::with_shards(8));
+
+        let handles: Vec<_> = (0..8)
+            .map(|t| {
+                let exists_map = Arc::clone(&exists_map);
+                std::thread::spawn(move || {
+                    for i in t * 100..(t + 1) * 100 {
+                        exists_map.insert(i, i);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        for i in 0..800 {
+            assert_eq!(exists_map.get(&i), Some(i));
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! The `entry` API for [`ExistsMap`](crate::ExistsMap), mirroring
+//! `std::collections::hash_map::Entry`.
+
+use crate::ExistsItem;
+use std::collections::hash_map;
+
+/// A view into a single entry in an [`ExistsMap`](crate::ExistsMap), which
+/// may either be vacant or occupied.
+///
+/// This is obtained via [`ExistsMap::entry`](crate::ExistsMap::entry).
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    /// Ensures a value is in the entry by inserting `default` if empty, and
+    /// returns a mutable reference to the value.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of `default`
+    /// if empty, and returns a mutable reference to the value.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default value if
+    /// empty, and returns a mutable reference to the value.
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+/// A view into an occupied entry in an [`ExistsMap`](crate::ExistsMap).
+pub struct OccupiedEntry<'a, K, V> {
+    pub(crate) inner: hash_map::OccupiedEntry<'a, ExistsItem<K>, V>,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    pub fn get(&self) -> &V {
+        self.inner.get()
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        self.inner.get_mut()
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    pub fn remove(self) -> V {
+        self.inner.remove()
+    }
+}
+
+/// A view into a vacant entry in an [`ExistsMap`](crate::ExistsMap).
+pub struct VacantEntry<'a, K, V> {
+    pub(crate) inner: hash_map::VacantEntry<'a, ExistsItem<K>, V>,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    /// Sets the value of the entry, returning a mutable reference to it.
+    ///
+    /// The key has already been hashed by
+    /// [`ExistsMap::entry`](crate::ExistsMap::entry), so this does not hash
+    /// it again.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.inner.insert(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ExistsMap;
+
+    #[test]
+    fn test_entry_or_insert_inserts_when_vacant() {
+        let mut exists_map = ExistsMap::<i32, i32>::default();
+        *exists_map.entry(1).or_insert(10) += 1;
+        assert_eq!(*exists_map.get(&1).unwrap(), 11);
+    }
+
+    #[test]
+    fn test_entry_or_insert_keeps_existing_when_occupied() {
+        let mut exists_map = ExistsMap::<i32, i32>::default();
+        exists_map.insert(1, 10);
+        *exists_map.entry(1).or_insert(99) += 1;
+        assert_eq!(*exists_map.get(&1).unwrap(), 11);
+    }
+
+    #[test]
+    fn test_entry_or_default() {
+        let mut exists_map = ExistsMap::<i32, Vec<i32>>::default();
+        exists_map.entry(1).or_default().push(5);
+        exists_map.entry(1).or_default().push(6);
+        assert_eq!(exists_map.get(&1).unwrap(), &vec![5, 6]);
+    }
+
+    #[test]
+    fn test_entry_and_modify() {
+        let mut exists_map = ExistsMap::<i32, i32>::default();
+        exists_map.insert(1, 1);
+        exists_map.entry(1).and_modify(|v| *v += 10).or_insert(0);
+        exists_map.entry(2).and_modify(|v| *v += 10).or_insert(0);
+        assert_eq!(*exists_map.get(&1).unwrap(), 11);
+        assert_eq!(*exists_map.get(&2).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_occupied_entry_remove() {
+        let mut exists_map = ExistsMap::<i32, i32>::default();
+        exists_map.insert(1, 1);
+        let crate::Entry::Occupied(entry) = exists_map.entry(1) else {
+            panic!("expected an occupied entry");
+        };
+        assert_eq!(entry.remove(), 1);
+        assert!(!exists_map.contains(&1));
+    }
+}
@@ -1,8 +1,18 @@
 #![allow(dead_code, unused)]
 
+mod concurrent;
+mod entry;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod verified;
+
+pub use concurrent::ConcurrentExistsMap;
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
+pub use verified::VerifiedExistsMap;
+
 #[derive(Clone)]
-struct ExistsItem<K> {
-    hashed_key: u64,
+pub(crate) struct ExistsItem<K> {
+    pub(crate) hashed_key: u64,
     _marker: std::marker::PhantomData<K>,
 }
 
@@ -41,12 +51,176 @@ impl<K> PartialEq for ExistsItem<K> {
 
 impl<K> Eq for ExistsItem<K> {}
 
+/// A `HashMap`-like container that stores only a 64-bit hash of each key
+/// instead of the key itself, trading a small, known collision probability
+/// for a lower memory footprint.
+///
+/// With a well-distributed 64-bit hash, two distinct keys colliding is
+/// unlikely until the map holds on the order of billions of entries (the
+/// birthday bound for 64 bits), but for untrusted or adversarial keyspaces
+/// it can happen far sooner: on collision, `insert` silently overwrites the
+/// existing entry and `get`/`contains` can return a false positive. If that
+/// risk is unacceptable, use [`VerifiedExistsMap`] instead, which retains
+/// the key and resolves collisions correctly at the cost of storing it.
 #[derive(Default, Clone)]
 pub struct ExistsMap<K, V> {
-    state: ahash::RandomState,
-    map: std::collections::HashMap<ExistsItem<K>, V>,
+    pub(crate) state: ahash::RandomState,
+    /// The seed the hasher was fixed to, if any. Only set by the
+    /// seed-fixing constructors; required to serialize the map, since the
+    /// stored hashes are only meaningful relative to this seed.
+    pub(crate) seed: Option<usize>,
+    pub(crate) map: std::collections::HashMap<ExistsItem<K>, V>,
+}
+
+impl<K, V> ExistsMap<K, V> {
+    /// Creates an empty `ExistsMap` with a randomly seeded hasher.
+    pub fn new() -> Self {
+        Self {
+            state: ahash::RandomState::new(),
+            seed: None,
+            map: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Creates an empty `ExistsMap` whose hasher is seeded deterministically
+    /// from `seed`, so that two maps built with the same seed hash keys the
+    /// same way. This is required for reproducible hashing, e.g. when
+    /// serializing an `ExistsMap` or in deterministic tests.
+    pub fn with_seed(seed: usize) -> Self {
+        Self {
+            state: ahash::RandomState::with_seed(seed),
+            seed: Some(seed),
+            map: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Creates an empty `ExistsMap` that uses the given `state` to hash
+    /// keys.
+    pub fn with_hasher(state: ahash::RandomState) -> Self {
+        Self {
+            state,
+            seed: None,
+            map: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Creates an empty `ExistsMap` with a randomly seeded hasher and space
+    /// reserved for at least `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            state: ahash::RandomState::new(),
+            seed: None,
+            map: std::collections::HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Like [`with_capacity`](Self::with_capacity), but also fixes the
+    /// hasher seed as in [`with_seed`](Self::with_seed).
+    pub fn with_capacity_and_seed(capacity: usize, seed: usize) -> Self {
+        Self {
+            state: ahash::RandomState::with_seed(seed),
+            seed: Some(seed),
+            map: std::collections::HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Removes all entries from the map, keeping the allocated capacity.
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Returns the number of entries the map can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more entries.
+    pub fn reserve(&mut self, additional: usize) {
+        self.map.reserve(additional)
+    }
+
+    /// Shrinks the capacity of the map as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.map.shrink_to_fit()
+    }
+
+    /// Returns an iterator over the values, in arbitrary order.
+    ///
+    /// `ExistsMap` discards keys, so unlike `HashMap` there is no `keys` or
+    /// `iter` over `(K, V)` pairs.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.map.values()
+    }
+
+    /// Returns an iterator that allows modifying each value, in arbitrary
+    /// order.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.map.values_mut()
+    }
+
+    /// Creates a consuming iterator over the values, in arbitrary order.
+    pub fn into_values(self) -> impl Iterator<Item = V> {
+        self.map.into_values()
+    }
+
+    /// Clears the map, returning all values as an iterator.
+    ///
+    /// Keeps the allocated capacity.
+    pub fn drain(&mut self) -> impl Iterator<Item = V> + '_ {
+        self.map.drain().map(|(_, v)| v)
+    }
+
+    /// Retains only the values for which `f` returns `true`, removing the
+    /// rest along with their hashed keys.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut V) -> bool,
+    {
+        self.map.retain(|_, v| f(v))
+    }
+
+    /// Merges `other` into `self`.
+    ///
+    /// Fails if the two maps were not built with the same fixed seed:
+    /// hashes produced under different seeds aren't comparable, so merging
+    /// them would silently corrupt lookups.
+    pub fn merge(&mut self, other: ExistsMap<K, V>) -> Result<(), SeedMismatch> {
+        match (self.seed, other.seed) {
+            (Some(a), Some(b)) if a == b => {
+                self.map.extend(other.map);
+                Ok(())
+            }
+            _ => Err(SeedMismatch),
+        }
+    }
+}
+
+/// Returned by [`ExistsMap::merge`] when the two maps were built with
+/// different (or unfixed) hasher seeds and cannot be merged safely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeedMismatch;
+
+impl std::fmt::Display for SeedMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "cannot merge ExistsMaps built with different or unfixed hasher seeds"
+        )
+    }
 }
 
+impl std::error::Error for SeedMismatch {}
+
 impl<K, V> ExistsMap<K, V>
 where
     K: std::hash::Hash,
@@ -56,6 +230,20 @@ where
         self.map.insert(item, value)
     }
 
+    /// Gets the entry for `key` in the map, allowing insertion, in-place
+    /// modification, or removal without hashing the key twice.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let item = ExistsItem::new(&key, &self.state);
+        match self.map.entry(item) {
+            std::collections::hash_map::Entry::Occupied(inner) => {
+                Entry::Occupied(OccupiedEntry { inner })
+            }
+            std::collections::hash_map::Entry::Vacant(inner) => {
+                Entry::Vacant(VacantEntry { inner })
+            }
+        }
+    }
+
     pub fn get(&self, key: &K) -> Option<&V> {
         let key = ExistsItem::new(key, &self.state);
         self.map.get(&key)
@@ -87,10 +275,7 @@ mod test {
     use super::*;
     #[test]
     fn test_exists_map_basic() {
-        let mut exists_map = ExistsMap::<i32, i32> {
-            map: std::collections::HashMap::new(),
-            state: ahash::RandomState::with_seed(fastrand::usize(..)),
-        };
+        let mut exists_map = ExistsMap::<i32, i32>::with_seed(fastrand::usize(..));
 
         for i in 50..=100 {
             exists_map.insert(i, i);
@@ -126,10 +311,7 @@ mod test {
 
     #[test]
     fn test_exists_map_string() {
-        let mut exists_map = ExistsMap::<String, String> {
-            map: std::collections::HashMap::new(),
-            state: ahash::RandomState::with_seed(fastrand::usize(..)),
-        };
+        let mut exists_map = ExistsMap::<String, String>::with_seed(fastrand::usize(..));
 
         exists_map.insert("hello".into(), "hello".into());
         exists_map.insert("world".into(), "world".into());
@@ -171,4 +353,98 @@ mod test {
         assert_eq!(None, exists_map.get(&"foo".into()));
         assert!(!exists_map.contains("foo"));
     }
+
+    #[test]
+    fn test_exists_map_constructors_and_capacity() {
+        let mut exists_map = ExistsMap::<i32, i32>::with_capacity_and_seed(16, 0);
+        assert!(exists_map.is_empty());
+        assert_eq!(exists_map.len(), 0);
+        assert!(exists_map.capacity() >= 16);
+
+        exists_map.insert(1, 1);
+        exists_map.insert(2, 2);
+        assert_eq!(exists_map.len(), 2);
+        assert!(!exists_map.is_empty());
+
+        exists_map.reserve(64);
+        assert!(exists_map.capacity() >= 64);
+        exists_map.shrink_to_fit();
+
+        exists_map.clear();
+        assert!(exists_map.is_empty());
+        assert_eq!(exists_map.len(), 0);
+        assert_eq!(None, exists_map.get(&1));
+    }
+
+    #[test]
+    fn test_exists_map_values_drain_retain() {
+        let mut exists_map = ExistsMap::<i32, i32>::with_seed(0);
+        for i in 0..10 {
+            exists_map.insert(i, i);
+        }
+
+        for value in exists_map.values_mut() {
+            *value *= 10;
+        }
+        let mut values: Vec<i32> = exists_map.values().copied().collect();
+        values.sort_unstable();
+        assert_eq!(values, (0..10).map(|i| i * 10).collect::<Vec<_>>());
+
+        exists_map.retain(|v| *v % 20 == 0);
+        let mut values: Vec<i32> = exists_map.values().copied().collect();
+        values.sort_unstable();
+        assert_eq!(values, vec![0, 20, 40, 60, 80]);
+
+        let mut drained: Vec<i32> = exists_map.drain().collect();
+        drained.sort_unstable();
+        assert_eq!(drained, vec![0, 20, 40, 60, 80]);
+        assert!(exists_map.is_empty());
+    }
+
+    #[test]
+    fn test_exists_map_into_values() {
+        let mut exists_map = ExistsMap::<i32, i32>::with_seed(0);
+        exists_map.insert(1, 1);
+        exists_map.insert(2, 2);
+        let mut values: Vec<i32> = exists_map.into_values().collect();
+        values.sort_unstable();
+        assert_eq!(values, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_exists_map_with_seed_is_deterministic() {
+        let mut a = ExistsMap::<i32, i32>::with_seed(42);
+        let mut b = ExistsMap::<i32, i32>::with_seed(42);
+        a.insert(1, 1);
+        b.insert(1, 1);
+        assert_eq!(a.state.hash_one(1), b.state.hash_one(1));
+        assert_eq!(*a.get(&1).unwrap(), *b.get(&1).unwrap());
+        assert!(b.contains(&1));
+    }
+
+    #[test]
+    fn test_merge_rejects_different_seeds() {
+        let mut a = ExistsMap::<i32, i32>::with_seed(1);
+        let b = ExistsMap::<i32, i32>::with_seed(2);
+        assert_eq!(a.merge(b), Err(SeedMismatch));
+    }
+
+    #[test]
+    fn test_merge_rejects_unfixed_seeds() {
+        let mut a = ExistsMap::<i32, i32>::new();
+        let b = ExistsMap::<i32, i32>::new();
+        assert_eq!(a.merge(b), Err(SeedMismatch));
+    }
+
+    #[test]
+    fn test_merge_accepts_matching_seeds() {
+        let mut a = ExistsMap::<i32, i32>::with_seed(1);
+        a.insert(1, 1);
+        let mut b = ExistsMap::<i32, i32>::with_seed(1);
+        b.insert(2, 2);
+
+        a.merge(b).unwrap();
+        assert_eq!(a.get(&1), Some(&1));
+        assert_eq!(a.get(&2), Some(&2));
+    }
 }
@@ -0,0 +1,97 @@
+//! Optional `serde` support for [`ExistsMap`](crate::ExistsMap).
+//!
+//! `ExistsMap` only stores a hash of each key, and that hash is only
+//! meaningful relative to the `ahash::RandomState` seed that produced it. A
+//! naive round-trip that re-seeds on deserialize would silently break every
+//! subsequent `get`/`contains`, so the serialized form carries the seed and
+//! deserialization reconstructs the exact same hasher from it instead of
+//! re-hashing anything. This is why serializing requires the map to have
+//! been built with a seed-fixing constructor, e.g.
+//! [`ExistsMap::with_seed`](crate::ExistsMap::with_seed).
+
+use crate::{ExistsItem, ExistsMap};
+use serde::ser::{Error as SerError, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl<K, V> Serialize for ExistsMap<K, V>
+where
+    V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let seed = self.seed.ok_or_else(|| {
+            S::Error::custom(
+                "ExistsMap can only be serialized if it was built with a fixed seed, \
+                 e.g. via ExistsMap::with_seed",
+            )
+        })?;
+        let entries: Vec<(u64, &V)> = self
+            .map
+            .iter()
+            .map(|(item, value)| (item.hashed_key, value))
+            .collect();
+
+        let mut out = serializer.serialize_struct("ExistsMap", 2)?;
+        out.serialize_field("seed", &seed)?;
+        out.serialize_field("entries", &entries)?;
+        out.end()
+    }
+}
+
+#[derive(Deserialize)]
+struct ExistsMapData<V> {
+    seed: usize,
+    entries: Vec<(u64, V)>,
+}
+
+impl<'de, K, V> Deserialize<'de> for ExistsMap<K, V>
+where
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = ExistsMapData::<V>::deserialize(deserializer)?;
+        let map = data
+            .entries
+            .into_iter()
+            .map(|(hashed_key, value)| (ExistsItem::from(hashed_key), value))
+            .collect();
+
+        Ok(ExistsMap {
+            state: ahash::RandomState::with_seed(data.seed),
+            seed: Some(data.seed),
+            map,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_preserves_lookups() {
+        let mut exists_map = ExistsMap::<i32, i32>::with_seed(7);
+        for i in 0..20 {
+            exists_map.insert(i, i * 2);
+        }
+
+        let json = serde_json::to_string(&exists_map).unwrap();
+        let restored: ExistsMap<i32, i32> = serde_json::from_str(&json).unwrap();
+
+        for i in 0..20 {
+            assert_eq!(restored.get(&i), Some(&(i * 2)));
+        }
+        assert_eq!(restored.get(&20), None);
+    }
+
+    #[test]
+    fn test_serialize_without_fixed_seed_errors() {
+        let exists_map = ExistsMap::<i32, i32>::new();
+        assert!(serde_json::to_string(&exists_map).is_err());
+    }
+}
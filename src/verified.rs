@@ -0,0 +1,147 @@
+//! A collision-resistant variant of [`ExistsMap`](crate::ExistsMap).
+//!
+//! `ExistsMap` discards the original key and stores only the 64-bit hash in
+//! `ExistsItem`, so two distinct keys that happen to hash to the same `u64`
+//! will collide: the second `insert` silently overwrites the first, and
+//! `get`/`contains` can return a false positive for a key that was never
+//! inserted. For a uniformly distributed 64-bit hash this only becomes a
+//! practical concern past roughly 2^32 stored keys (the birthday bound), but
+//! for untrusted or adversarial keyspaces it can happen far sooner.
+//!
+//! `VerifiedExistsMap` trades the memory savings of the key-less design for
+//! correctness: each hash bucket keeps the (short, usually single-element)
+//! list of keys that share it, and every lookup confirms an exact key match,
+//! so genuine hash collisions resolve correctly instead of colliding
+//! silently.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Like [`ExistsMap`](crate::ExistsMap), but retains the original key so
+/// that real hash collisions are resolved instead of silently colliding.
+///
+/// Use this over `ExistsMap` whenever keys come from an untrusted source, or
+/// the keyspace is large enough that a 64-bit hash collision is a realistic
+/// possibility. The cost is storing one `K` per entry, plus the 64-bit hash
+/// and a heap-allocated `Vec` for each distinct hash, so it uses more memory
+/// than a plain `HashMap`.
+#[derive(Default, Clone)]
+pub struct VerifiedExistsMap<K, V> {
+    state: ahash::RandomState,
+    map: HashMap<u64, Vec<(K, V)>>,
+}
+
+impl<K, V> VerifiedExistsMap<K, V>
+where
+    K: Hash + Eq,
+{
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let hashed_key = self.state.hash_one(&key);
+        let bucket = self.map.entry(hashed_key).or_default();
+        if let Some(slot) = bucket.iter_mut().find(|(k, _)| *k == key) {
+            return Some(std::mem::replace(&mut slot.1, value));
+        }
+        bucket.push((key, value));
+        None
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hashed_key = self.state.hash_one(key);
+        self.map
+            .get(&hashed_key)?
+            .iter()
+            .find(|(k, _)| k.borrow() == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hashed_key = self.state.hash_one(key);
+        let bucket = self.map.get_mut(&hashed_key)?;
+        let pos = bucket.iter().position(|(k, _)| k.borrow() == key)?;
+        let (_, value) = bucket.swap_remove(pos);
+        if bucket.is_empty() {
+            self.map.remove(&hashed_key);
+        }
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_verified_exists_map_basic() {
+        let mut exists_map = VerifiedExistsMap::<i32, i32>::default();
+
+        for i in 50..=100 {
+            exists_map.insert(i, i);
+        }
+        assert!(exists_map.contains(&50));
+        assert!(exists_map.contains(&75));
+        assert!(exists_map.contains(&100));
+        assert_eq!(*exists_map.get(&50).unwrap(), 50);
+        assert_eq!(None, exists_map.get(&-1));
+        assert_eq!(None, exists_map.get(&101));
+
+        let val = exists_map.remove(&50).unwrap();
+        assert_eq!(val, 50);
+        assert_eq!(None, exists_map.get(&50));
+        assert!(!exists_map.contains(&50));
+    }
+
+    #[test]
+    fn test_verified_exists_map_string() {
+        let mut exists_map = VerifiedExistsMap::<String, String>::default();
+
+        exists_map.insert("hello".into(), "hello".into());
+        exists_map.insert("world".into(), "world".into());
+
+        assert!(exists_map.contains("hello"));
+        assert!(exists_map.contains("world"));
+        assert!(!exists_map.contains("baz"));
+
+        let val = exists_map.remove("hello").unwrap();
+        assert_eq!(val, "hello");
+        assert!(!exists_map.contains("hello"));
+    }
+
+    #[test]
+    fn test_verified_exists_map_resolves_hash_collision() {
+        // Plant a `2` under the bucket that `1` will hash into, simulating
+        // two distinct keys that collide on their 64-bit hash. A plain
+        // ExistsMap would let inserting `1` clobber the entry for `2`;
+        // VerifiedExistsMap's key comparison must keep them both, in the
+        // same bucket.
+        let state = ahash::RandomState::with_seed(0);
+        let hashed_key = state.hash_one(1);
+        let mut exists_map = VerifiedExistsMap::<i32, i32> {
+            map: HashMap::from([(hashed_key, vec![(2, 2)])]),
+            state,
+        };
+
+        exists_map.insert(1, 1);
+
+        let bucket = exists_map.map.get(&hashed_key).unwrap();
+        assert_eq!(bucket.len(), 2);
+        assert!(bucket.contains(&(1, 1)));
+        assert!(bucket.contains(&(2, 2)));
+    }
+}